@@ -0,0 +1,16 @@
+use std::fs;
+
+const HIGHSCORE_FILE: &str = "highscore.txt";
+
+// 从文件中读取历史最高分，文件缺失或内容损坏时返回0
+pub fn load_high_score() -> i32 {
+    fs::read_to_string(HIGHSCORE_FILE)
+        .ok()
+        .and_then(|content| content.trim().parse().ok())
+        .unwrap_or(0)
+}
+
+// 将历史最高分写入文件
+pub fn save_high_score(high_score: i32) {
+    let _ = fs::write(HIGHSCORE_FILE, high_score.to_string());
+}
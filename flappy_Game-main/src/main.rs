@@ -1,12 +1,15 @@
+mod highscore;
 mod obstacle;
 mod player;
 mod sound;
 
 use bracket_lib::prelude::*;
+use highscore::{load_high_score, save_high_score};
 use image::*;
 use obstacle::Obstacle;
 use player::Player;
 use sound::{start_sound_thread, start_bgm_thread, SoundEffect};
+use std::collections::VecDeque;
 use std::sync::mpsc::Sender;
 
 // 按钮动作枚举
@@ -17,6 +20,8 @@ enum ButtonAction {
     ToggleAudio,
     ToggleMusic,
     Restart,
+    Resume,
+    CycleDifficulty,
 }
 
 // 按钮结构体
@@ -79,26 +84,88 @@ impl Button {
 enum GameMode {
     Menu,
     Playing,
+    Paused,
     End,
 }
 
+// 难度档位
+#[derive(Clone, Copy, PartialEq)]
+enum Difficulty {
+    Easy,
+    Normal,
+    Hard,
+}
+
+impl Difficulty {
+    // 每隔多少毫秒更新一次物理
+    fn frame_duration(&self) -> f32 {
+        match self {
+            Difficulty::Easy => 90.0,
+            Difficulty::Normal => 75.0,
+            Difficulty::Hard => 60.0,
+        }
+    }
+
+    // 相邻障碍物之间的间隔
+    fn obstacle_interval(&self) -> i32 {
+        match self {
+            Difficulty::Easy => 36,
+            Difficulty::Normal => 30,
+            Difficulty::Hard => 24,
+        }
+    }
+
+    // 初始洞口大小
+    fn starting_gap(&self) -> i32 {
+        match self {
+            Difficulty::Easy => 24,
+            Difficulty::Normal => 20,
+            Difficulty::Hard => 16,
+        }
+    }
+
+    // 洞口最小大小
+    fn min_gap(&self) -> i32 {
+        match self {
+            Difficulty::Easy => 8,
+            Difficulty::Normal => 5,
+            Difficulty::Hard => 4,
+        }
+    }
+
+    // 切换到下一档难度
+    fn next(&self) -> Self {
+        match self {
+            Difficulty::Easy => Difficulty::Normal,
+            Difficulty::Normal => Difficulty::Hard,
+            Difficulty::Hard => Difficulty::Easy,
+        }
+    }
+
+    fn label(&self) -> &'static str {
+        match self {
+            Difficulty::Easy => "Easy",
+            Difficulty::Normal => "Normal",
+            Difficulty::Hard => "Hard",
+        }
+    }
+}
+
 /// 游戏屏幕宽度
 const SCREEN_WIDTH: i32 = 80;
 /// 游戏屏幕高度
 const SCREEN_HEIGHT: i32 = 50;
-/// 每隔75毫秒做一些事情
-const FRAME_DURATION: f32 = 75.0;
 /// 初始障碍物数量
 const INITIAL_OBSTACLES: usize = 3;
-/// 障碍物间隔
-const OBSTACLE_INTERVAL: i32 = 30;
 
 struct State {
     player: Player,
     frame_time: f32,
     mode: GameMode,
-    obstacles: Vec<Obstacle>,
+    obstacles: VecDeque<Obstacle>,
     score: i32, // 分数
+    high_score: i32, // 历史最高分
+    new_record: bool, // 本局是否刷新了历史最高分
     sound_tx: Sender<SoundEffect>,
     audio_enabled: bool, // 音效开关
     music_enabled: bool, // 音乐开关
@@ -106,26 +173,31 @@ struct State {
     bgm_stop_tx: Sender<bool>, // 停止背景音乐
     buttons: Vec<Button>, // 按钮列表
     last_obstacle_gap_y: Option<i32>, // 最后一个障碍物的中心点位置
+    difficulty: Difficulty, // 当前难度
+    show_trajectory: bool, // 是否显示轨迹预测
 }
 
 impl State {
     fn new() -> Self {
         let sound_tx = start_sound_thread();
         let (bgm_play_tx, bgm_stop_tx) = start_bgm_thread();
+        let difficulty = Difficulty::Normal;
 
-        let mut obstacles = Vec::new();
+        let mut obstacles = VecDeque::new();
         let mut x = SCREEN_WIDTH;
         for _ in 0..INITIAL_OBSTACLES {
-            obstacles.push(Obstacle::new(x, 0));
-            x += OBSTACLE_INTERVAL;
+            obstacles.push_back(Obstacle::new(x, 0));
+            x += difficulty.obstacle_interval();
         }
-        
+
         State {
             player: Player::new(5, 25),
             frame_time: 0.0,
             mode: GameMode::Menu,
             obstacles,
             score: 0,
+            high_score: load_high_score(),
+            new_record: false,
             sound_tx,
             audio_enabled: true,
             music_enabled: true,
@@ -133,6 +205,8 @@ impl State {
             bgm_stop_tx,
             buttons: Vec::new(),
             last_obstacle_gap_y: None,
+            difficulty,
+            show_trajectory: false,
         }
     }
 
@@ -155,7 +229,7 @@ impl State {
     // 创建受限制的障碍物
     fn create_obstacle_with_constraint(&mut self, x: i32, score: i32) -> Obstacle {
         let mut random = RandomNumberGenerator::new();
-        let size = i32::max(5, 20 - score); // 洞口最小为5
+        let size = i32::max(self.difficulty.min_gap(), self.difficulty.starting_gap() - score);
         let half_size = size / 2;
         let min_gap_y = half_size + 2;
         let max_gap_y = SCREEN_HEIGHT - half_size - 2;
@@ -182,6 +256,7 @@ impl State {
         self.buttons.push(Button::new(30, 20, 20, 3, "Quit Game".to_string(), ButtonAction::Quit));
         self.buttons.push(Button::new(30, 25, 20, 3, format!("Audio: {}", if self.audio_enabled { "ON" } else { "OFF" }), ButtonAction::ToggleAudio));
         self.buttons.push(Button::new(30, 30, 20, 3, format!("Music: {}", if self.music_enabled { "ON" } else { "OFF" }), ButtonAction::ToggleMusic));
+        self.buttons.push(Button::new(30, 35, 20, 3, format!("Difficulty: {}", self.difficulty.label()), ButtonAction::CycleDifficulty));
     }
 
     // 创建游戏结束按钮
@@ -193,8 +268,17 @@ impl State {
         self.buttons.push(Button::new(30, 35, 20, 3, format!("Music: {}", if self.music_enabled { "ON" } else { "OFF" }), ButtonAction::ToggleMusic));
     }
 
+    // 创建暂停菜单按钮
+    fn create_pause_buttons(&mut self) {
+        self.buttons.clear();
+        self.buttons.push(Button::new(30, 15, 20, 3, "Resume".to_string(), ButtonAction::Resume));
+        self.buttons.push(Button::new(30, 20, 20, 3, "Restart".to_string(), ButtonAction::Restart));
+        self.buttons.push(Button::new(30, 25, 20, 3, "Quit".to_string(), ButtonAction::Quit));
+    }
+
     // 处理按钮点击
     fn handle_button_click(&mut self, action: ButtonAction, ctx: &mut BTerm) {
+        self.play_sound(SoundEffect::ButtonClick);
         match action {
             ButtonAction::Play => self.restart(),
             ButtonAction::Quit => ctx.quitting = true,
@@ -218,6 +302,16 @@ impl State {
                 }
             },
             ButtonAction::Restart => self.restart(),
+            ButtonAction::Resume => self.resume(),
+            ButtonAction::CycleDifficulty => {
+                self.difficulty = self.difficulty.next();
+                // 重新创建按钮以更新文本
+                match self.mode {
+                    GameMode::Menu => self.create_menu_buttons(),
+                    GameMode::End => self.create_end_buttons(),
+                    _ => {}
+                }
+            },
         }
     }
 
@@ -225,11 +319,19 @@ impl State {
     fn handle_mouse(&mut self, ctx: &mut BTerm) {
         let (mouse_x, mouse_y) = ctx.mouse_pos();
         
-        // 重置所有按钮的悬停状态
+        // 重置所有按钮的悬停状态，悬停从false变为true时播放一次提示音
+        let mut just_hovered = false;
         for button in &mut self.buttons {
+            let was_hover = button.hover;
             button.hover = button.contains_point(mouse_x, mouse_y);
+            if button.hover && !was_hover {
+                just_hovered = true;
+            }
         }
-        
+        if just_hovered {
+            self.play_sound(SoundEffect::ButtonHover);
+        }
+
         // 检查鼠标点击
         if ctx.left_click {
             for button in &self.buttons {
@@ -252,6 +354,7 @@ impl State {
         
         // 绘制标题
         ctx.print_centered(5, "Welcome to Flappy Dragon！");
+        ctx.print_centered(6, &format!("Best: {}", self.high_score));
         ctx.print_centered(7, "Click buttons or use keyboard shortcuts:");
         ctx.print_centered(8, "P - Play, Q - Quit, M - Audio, B - Music");
         
@@ -285,20 +388,64 @@ impl State {
         }
     }
 
+    // 固定时间步长更新：推进物理、滚动障碍物窗口、计分并检测碰撞
+    fn update_physics(&mut self) {
+        self.player.gravity_and_move();
+
+        // 前面的障碍物完全滚出屏幕（已被越过）后，将其从队首弹出并计分，
+        // 同时在队尾补充一个新的，让障碍物窗口保持固定大小
+        while self.obstacles.front().map_or(false, |o| self.player.x > o.x) {
+            self.obstacles.pop_front();
+
+            self.score += 1;
+            if self.score > self.high_score {
+                self.high_score = self.score;
+                self.new_record = true;
+            }
+
+            let new_x = self.obstacles.back().map(|o| o.x).unwrap_or(self.player.x + SCREEN_WIDTH)
+                + self.difficulty.obstacle_interval();
+            let obstacle = self.create_obstacle_with_constraint(new_x, self.score);
+            self.obstacles.push_back(obstacle);
+        }
+
+        // 碰撞检测
+        if self.obstacles.iter().any(|o| o.hit_obstacle(&self.player)) {
+            self.mode = GameMode::End;
+            self.play_sound(SoundEffect::Hit);
+            save_high_score(self.high_score);
+            return;
+        }
+
+        // 如果y 大于游戏高度，就是坠地，则游戏结束
+        if self.player.y > SCREEN_HEIGHT {
+            self.mode = GameMode::End;
+            self.play_sound(SoundEffect::GameOver);
+            save_high_score(self.high_score);
+        }
+    }
+
     fn play(&mut self, ctx: &mut BTerm) {
         ctx.cls_bg(NAVY);
         // frame_time_ms 记录了每次调用tick所经过的时间
         self.frame_time += ctx.frame_time_ms;
-        // 向前移动并且重力增加
-        if self.frame_time > FRAME_DURATION {
+        // 固定时间步长更新物理、障碍物滚动、计分与碰撞检测
+        if self.frame_time > self.difficulty.frame_duration() {
             self.frame_time = 0.0;
-            self.player.gravity_and_move();
+            self.update_physics();
         }
         // 空格触发，往上飞
         if let Some(VirtualKeyCode::Space) = ctx.key {
             self.player.flap();
             self.play_sound(SoundEffect::Flap);
         }
+        // P键暂停游戏
+        if let Some(VirtualKeyCode::P) = ctx.key {
+            self.mode = GameMode::Paused;
+            self.buttons.clear();
+            let _ = self.bgm_stop_tx.send(true);
+            return;
+        }
         // 音频切换
         if let Some(VirtualKeyCode::M) = ctx.key {
             self.audio_enabled = !self.audio_enabled;
@@ -308,9 +455,13 @@ impl State {
             self.music_enabled = !self.music_enabled;
             self.set_music(self.music_enabled);
         }
+        // T键切换轨迹预测
+        if let Some(VirtualKeyCode::T) = ctx.key {
+            self.show_trajectory = !self.show_trajectory;
+        }
         // 渲染
         self.player.render(ctx);
-        ctx.print(0, 0, "Press Space to Flap");
+        ctx.print(0, 0, "Press Space to Flap, P to Pause, T to Preview Trajectory");
         ctx.print(0, 1, &format!("Score: {}", self.score));
         ctx.print(0, 2, &format!("Audio: {}  Music: {}", if self.audio_enabled { "ON" } else { "OFF" }, if self.music_enabled { "ON" } else { "OFF" }));
 
@@ -319,36 +470,91 @@ impl State {
             obstacle.render(ctx, self.player.x);
         }
 
-        // 检查是否越过障碍物
-        let mut passed = None;
-        let mut hit_obstacle = false;
-        for (i, obstacle) in self.obstacles.iter_mut().enumerate() {
-            if self.player.x > obstacle.x {
-                passed = Some(i);
+        // 渲染轨迹预测
+        if self.show_trajectory {
+            self.render_trajectory(ctx);
+        }
+    }
+
+    // 渲染轨迹预测：模拟若玩家不操作恐龙接下来几步会如何下落，遇到障碍物实心部分提前停止
+    fn render_trajectory(&self, ctx: &mut BTerm) {
+        const TRAJECTORY_STEPS: i32 = 40;
+
+        let mut ghost = self.player.clone();
+
+        for _ in 0..TRAJECTORY_STEPS {
+            ghost.gravity_and_move();
+
+            let screen_x = ghost.x - self.player.x;
+            if screen_x < 0 || screen_x >= SCREEN_WIDTH || ghost.y < 0 || ghost.y > SCREEN_HEIGHT {
+                break;
             }
-            if obstacle.hit_obstacle(&self.player) {
-                hit_obstacle = true;
+
+            let blocked = self.obstacles.iter().any(|obstacle| {
+                obstacle.x == ghost.x && {
+                    let half_size = obstacle.size / 2;
+                    ghost.y < obstacle.gap_y - half_size || ghost.y > obstacle.gap_y + half_size
+                }
+            });
+
+            ctx.set(screen_x, ghost.y, DARK_GRAY, BLACK, to_cp437('·'));
+
+            if blocked {
+                break;
             }
         }
-        
-        // 处理碰撞
-        if hit_obstacle {
-            self.mode = GameMode::End;
-            self.play_sound(SoundEffect::Hit);
+    }
+
+    fn paused(&mut self, ctx: &mut BTerm) {
+        ctx.cls_bg(NAVY);
+
+        // 冻结画面：只渲染上一帧的状态，不更新物理或障碍物
+        for obstacle in &mut self.obstacles {
+            obstacle.render(ctx, self.player.x);
         }
-        
-        if let Some(i) = passed {
-            self.score += 1;
-            // 新障碍物x取当前所有障碍物最大x+OBSTACLE_INTERVAL
-            let max_x = self.obstacles.iter().map(|o| o.x).max().unwrap_or(SCREEN_WIDTH);
-            let new_x = max_x + OBSTACLE_INTERVAL;
-            self.obstacles[i] = self.create_obstacle_with_constraint(new_x, self.score);
+        self.player.render(ctx);
+
+        // 将整个画面调暗，模拟定格效果
+        for x in 0..SCREEN_WIDTH {
+            for y in 0..SCREEN_HEIGHT {
+                ctx.set_bg(x, y, DARK_GRAY);
+            }
         }
 
-        // 如果y 大于游戏高度，就是坠地，则游戏结束
-        if self.player.y > SCREEN_HEIGHT {
-            self.mode = GameMode::End;
-            self.play_sound(SoundEffect::GameOver);
+        // 创建按钮（如果还没有创建）
+        if self.buttons.is_empty() {
+            self.create_pause_buttons();
+        }
+
+        ctx.print_centered(10, "Paused");
+        ctx.print_centered(12, "Click buttons or use keyboard shortcuts:");
+        ctx.print_centered(13, "P - Resume, R - Restart, Q - Quit");
+
+        // 绘制按钮
+        for button in &self.buttons {
+            button.render(ctx);
+        }
+
+        // 处理鼠标事件
+        self.handle_mouse(ctx);
+
+        // 处理键盘事件（保持向后兼容）
+        if let Some(key) = ctx.key {
+            match key {
+                VirtualKeyCode::P => self.resume(),
+                VirtualKeyCode::R => self.restart(),
+                VirtualKeyCode::Q => ctx.quitting = true,
+                _ => {}
+            }
+        }
+    }
+
+    // 从暂停状态恢复游戏
+    fn resume(&mut self) {
+        self.mode = GameMode::Playing;
+        self.buttons.clear();
+        if self.music_enabled {
+            self.set_music(true);
         }
     }
 
@@ -364,8 +570,12 @@ impl State {
         // 绘制游戏结束信息
         ctx.print_centered(5, "You are dead！");
         ctx.print_centered(6, &format!("You earned {} points", self.score));
-        ctx.print_centered(8, "Click buttons or use keyboard shortcuts:");
-        ctx.print_centered(9, "P - Play Again, Q - Quit, M - Audio, B - Music");
+        ctx.print_centered(7, &format!("Best: {}", self.high_score));
+        if self.new_record {
+            ctx.print_centered(8, "New Record!");
+        }
+        ctx.print_centered(10, "Click buttons or use keyboard shortcuts:");
+        ctx.print_centered(11, "P - Play Again, Q - Quit, M - Audio, B - Music");
         
         // 绘制按钮
         for button in &self.buttons {
@@ -404,10 +614,11 @@ impl State {
         let mut x = SCREEN_WIDTH;
         for _ in 0..INITIAL_OBSTACLES {
             let obstacle = self.create_obstacle_with_constraint(x, 0);
-            self.obstacles.push(obstacle);
-            x += OBSTACLE_INTERVAL;
+            self.obstacles.push_back(obstacle);
+            x += self.difficulty.obstacle_interval();
         }
         self.score = 0;
+        self.new_record = false;
         self.buttons.clear(); // 清空按钮列表
         
         // 如果音乐开启，播放背景音乐
@@ -434,6 +645,7 @@ impl GameState for State {
         match self.mode {
             GameMode::Menu => self.main_menu(ctx),
             GameMode::Playing => self.play(ctx),
+            GameMode::Paused => self.paused(ctx),
             GameMode::End => self.dead(ctx),
         }
     }
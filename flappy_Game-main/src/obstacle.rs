@@ -0,0 +1,45 @@
+use crate::player::Player;
+use crate::SCREEN_HEIGHT;
+use bracket_lib::prelude::*;
+
+/// 障碍物
+pub struct Obstacle {
+    pub x: i32,     // 世界坐标系中的x
+    pub gap_y: i32, // 空隙中心的y坐标
+    pub size: i32,  // 空隙大小
+}
+
+impl Obstacle {
+    pub fn new(x: i32, score: i32) -> Self {
+        let mut random = RandomNumberGenerator::new();
+        Obstacle {
+            x,
+            gap_y: random.range(10, 40),
+            size: i32::max(2, 20 - score),
+        }
+    }
+
+    pub fn render(&mut self, ctx: &mut BTerm, player_x: i32) {
+        let screen_x = self.x - player_x;
+        let half_size = self.size / 2;
+
+        // 绘制上半部分障碍物
+        for y in 0..self.gap_y - half_size {
+            ctx.set(screen_x, y, RED, BLACK, to_cp437('|'));
+        }
+
+        // 绘制下半部分障碍物
+        for y in self.gap_y + half_size..SCREEN_HEIGHT {
+            ctx.set(screen_x, y, RED, BLACK, to_cp437('|'));
+        }
+    }
+
+    // 检测是否撞到障碍物
+    pub fn hit_obstacle(&self, player: &Player) -> bool {
+        let half_size = self.size / 2;
+        let does_x_match = player.x == self.x;
+        let player_above_gap = player.y < self.gap_y - half_size;
+        let player_below_gap = player.y > self.gap_y + half_size;
+        does_x_match && (player_above_gap || player_below_gap)
+    }
+}
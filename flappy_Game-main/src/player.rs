@@ -0,0 +1,55 @@
+use bracket_lib::prelude::*;
+
+/// 玩家（小恐龙）
+#[derive(Clone)]
+pub struct Player {
+    pub x: i32,         // 世界坐标系中的x，随时间单调递增
+    pub y: i32,         // 屏幕坐标系中的y
+    pub velocity: f32,  // 垂直速度
+    flap_frame: bool, // 上升时用于在两帧振翅姿态间交替
+}
+
+impl Player {
+    pub fn new(x: i32, y: i32) -> Self {
+        Player {
+            x,
+            y,
+            velocity: 0.0,
+            flap_frame: false,
+        }
+    }
+
+    // 根据垂直速度选择恐龙的姿态：爬升、平飞或俯冲，爬升时在两帧间交替形成振翅动画
+    pub fn render(&mut self, ctx: &mut BTerm) {
+        let (glyph, color) = if self.velocity < -0.5 {
+            self.flap_frame = !self.flap_frame;
+            if self.flap_frame {
+                ('^', YELLOW)
+            } else {
+                ('@', YELLOW)
+            }
+        } else if self.velocity > 1.5 {
+            ('v', RED)
+        } else {
+            ('@', YELLOW)
+        };
+        ctx.set(0, self.y, color, BLACK, to_cp437(glyph));
+    }
+
+    // 重力作用，并向前移动
+    pub fn gravity_and_move(&mut self) {
+        if self.velocity < 2.0 {
+            self.velocity += 0.2;
+        }
+        self.y += self.velocity as i32;
+        self.x += 1;
+        if self.y < 0 {
+            self.y = 0;
+        }
+    }
+
+    // 拍打翅膀，向上飞
+    pub fn flap(&mut self) {
+        self.velocity = -2.0;
+    }
+}
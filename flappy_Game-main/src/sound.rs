@@ -6,6 +6,8 @@ pub enum SoundEffect {
     Hit,
     GameOver,
     BGM, // 添加背景音乐
+    ButtonHover, // 按钮悬停音效
+    ButtonClick, // 按钮点击音效
 }
 
 pub fn start_sound_thread() -> Sender<SoundEffect> {
@@ -30,6 +32,8 @@ pub fn start_sound_thread() -> Sender<SoundEffect> {
                             SoundEffect::Hit => println!("播放撞击音效"),
                             SoundEffect::GameOver => println!("播放游戏结束音效"),
                             SoundEffect::BGM => println!("播放背景音乐"),
+                            SoundEffect::ButtonHover => println!("播放按钮悬停音效"),
+                            SoundEffect::ButtonClick => println!("播放按钮点击音效"),
                         }
                     }
                 }
@@ -57,6 +61,8 @@ pub fn start_sound_thread() -> Sender<SoundEffect> {
                             SoundEffect::Flap => "assets/flap.wav",
                             SoundEffect::Hit => "assets/hit.wav",
                             SoundEffect::GameOver => "assets/gameover.wav",
+                            SoundEffect::ButtonHover => "assets/button_hover.wav",
+                            SoundEffect::ButtonClick => "assets/button_click.wav",
                             SoundEffect::BGM => unreachable!(), // 已经在上面处理了
                         };
                         